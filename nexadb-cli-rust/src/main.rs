@@ -6,7 +6,8 @@ use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::io::{Read, Write};
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read, Write};
 use std::net::TcpStream;
 const MAGIC: u32 = 0x4E455841; // "NEXA"
 const VERSION: u8 = 0x01;
@@ -19,6 +20,8 @@ const MSG_UPDATE: u8 = 0x04;
 const MSG_DELETE: u8 = 0x05;
 const MSG_QUERY: u8 = 0x06;
 const MSG_VECTOR_SEARCH: u8 = 0x07;
+const MSG_BATCH: u8 = 0x08;
+const MSG_STATS: u8 = 0x09;
 const MSG_LIST_COLLECTIONS: u8 = 0x20;
 
 // Response types
@@ -26,6 +29,11 @@ const MSG_SUCCESS: u8 = 0x81;
 const MSG_ERROR: u8 = 0x82;
 const MSG_NOT_FOUND: u8 = 0x83;
 
+/// Whether a message type mutates server state, and therefore must never be silently replayed.
+fn is_mutation(msg_type: u8) -> bool {
+    matches!(msg_type, MSG_CREATE | MSG_UPDATE | MSG_DELETE | MSG_BATCH)
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "nexa")]
 #[command(about = "Nexa - Interactive CLI for NexaDB", long_about = None)]
@@ -45,6 +53,44 @@ struct Args {
     /// Prompt for password
     #[arg(short, long)]
     password: bool,
+
+    /// Chat-completion endpoint used by the `ask` agent mode
+    #[arg(long, default_value = "http://localhost:11434/v1/chat/completions")]
+    llm_endpoint: String,
+
+    /// Model name requested from the LLM endpoint
+    #[arg(long, default_value = "llama3.1")]
+    llm_model: String,
+
+    /// Maximum tool-calling steps the `ask` agent loop will take before giving up
+    #[arg(long, default_value_t = 8)]
+    llm_max_steps: usize,
+
+    /// Run a single command non-interactively and exit
+    #[arg(long)]
+    exec: Option<String>,
+
+    /// Run a newline-separated script of commands non-interactively and exit
+    #[arg(long)]
+    file: Option<String>,
+
+    /// Result output format
+    #[arg(long, value_enum, default_value = "table")]
+    output: OutputFormat,
+
+    /// Maximum accepted response payload size in bytes (guards against a corrupt/malicious length header)
+    #[arg(long, default_value_t = 64 * 1024 * 1024)]
+    max_payload_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Human-readable, colorized (the interactive REPL default)
+    Table,
+    /// Pretty-printed JSON
+    Json,
+    /// Single-line JSON, one result per line
+    Compact,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,37 +99,107 @@ struct Message {
     data: Value,
 }
 
+/// Reconnect backoff before retrying a request whose socket dropped mid-flight.
+const RECONNECT_BACKOFF: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Runtime options for a `NexaClient`, beyond the bare connection coordinates.
+struct ClientConfig {
+    llm_endpoint: String,
+    llm_model: String,
+    llm_max_steps: usize,
+    output: OutputFormat,
+    max_payload_len: usize,
+    quiet: bool,
+}
+
 struct NexaClient {
     stream: TcpStream,
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
     current_collection: Option<String>,
+    llm_endpoint: String,
+    llm_model: String,
+    llm_max_steps: usize,
+    output: OutputFormat,
+    max_payload_len: usize,
 }
 
 impl NexaClient {
-    fn connect(host: &str, port: u16, username: &str, password: &str) -> Result<Self> {
-        println!("{}", format!("Connecting to {}:{}...", host, port).cyan());
+    fn connect(host: &str, port: u16, username: &str, password: &str, config: ClientConfig) -> Result<Self> {
+        if !config.quiet {
+            println!("{}", format!("Connecting to {}:{}...", host, port).cyan());
+        }
 
         let stream = TcpStream::connect(format!("{}:{}", host, port))
             .context("Failed to connect to NexaDB server")?;
 
         let mut client = Self {
             stream,
+            host: host.to_string(),
+            port,
+            username: username.to_string(),
+            password: password.to_string(),
             current_collection: None,
+            llm_endpoint: config.llm_endpoint,
+            llm_model: config.llm_model,
+            llm_max_steps: config.llm_max_steps,
+            output: config.output,
+            max_payload_len: config.max_payload_len,
         };
 
-        // Send handshake
-        let auth_data = serde_json::json!({
-            "username": username,
-            "password": password
-        });
-        client.send_message(MSG_CONNECT, &auth_data)?;
+        client.handshake()?;
 
-        println!("{}", "âœ“ Connected to NexaDB v2.3.0".green().bold());
-        println!();
+        if !config.quiet {
+            println!("{}", "âœ“ Connected to NexaDB v2.3.0".green().bold());
+            println!();
+        }
 
         Ok(client)
     }
 
+    fn handshake(&mut self) -> Result<Value> {
+        let auth_data = serde_json::json!({
+            "username": self.username,
+            "password": self.password
+        });
+        self.send_message_once(MSG_CONNECT, &auth_data)
+    }
+
+    /// Re-dials the server and replays the `MSG_CONNECT` handshake after a dropped connection.
+    fn reconnect(&mut self) -> Result<()> {
+        std::thread::sleep(RECONNECT_BACKOFF);
+        self.stream = TcpStream::connect(format!("{}:{}", self.host, self.port))
+            .context("Failed to reconnect to NexaDB server")?;
+        self.handshake().context("Failed to re-authenticate after reconnecting")?;
+        Ok(())
+    }
+
+    /// Sends a request, transparently reconnecting and retrying once if the socket dropped.
+    /// Application-level errors (e.g. "not found") are returned as-is without reconnecting.
+    /// Writes (`create`/`update`/`delete`/`batch`) are never silently replayed: if the socket
+    /// drops on one, the server may already have applied it, so we reconnect for future
+    /// commands but surface the ambiguity instead of risking a duplicate write.
     fn send_message(&mut self, msg_type: u8, data: &Value) -> Result<Value> {
+        match self.send_message_once(msg_type, data) {
+            Ok(value) => Ok(value),
+            Err(e) if e.downcast_ref::<std::io::Error>().is_some() => {
+                self.reconnect()?;
+                if is_mutation(msg_type) {
+                    anyhow::bail!(
+                        "Connection dropped while sending a write; reconnected, but whether the \
+                         write was applied is unknown. Re-check state (e.g. query/count) before \
+                         retrying rather than resubmitting blindly."
+                    );
+                }
+                self.send_message_once(msg_type, data)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn send_message_once(&mut self, msg_type: u8, data: &Value) -> Result<Value> {
         // Encode payload with MessagePack
         let mut payload = Vec::new();
         data.serialize(&mut Serializer::new(&mut payload))?;
@@ -118,6 +234,20 @@ impl NexaClient {
             anyhow::bail!("Invalid protocol magic: {:x}", magic);
         }
 
+        if payload_len > self.max_payload_len {
+            // Drain the declared payload off the socket (without allocating it) so the stream
+            // stays in sync for the next request instead of leaving trailing bytes that would
+            // be misread as the next response's header.
+            let mut drain = (&mut self.stream).take(payload_len as u64);
+            std::io::copy(&mut drain, &mut std::io::sink())
+                .context("Failed to drain oversized response payload")?;
+            anyhow::bail!(
+                "Response payload of {} bytes exceeds the {} byte limit",
+                payload_len,
+                self.max_payload_len
+            );
+        }
+
         // Read payload
         let mut payload = vec![0u8; payload_len];
         self.stream.read_exact(&mut payload)?;
@@ -141,6 +271,408 @@ impl NexaClient {
     }
 }
 
+/// JSON-Schema tool manifest describing the DB operations the agent may call.
+fn tool_manifest() -> Value {
+    serde_json::json!([
+        {
+            "type": "function",
+            "function": {
+                "name": "collections",
+                "description": "List all collections in the database.",
+                "parameters": {"type": "object", "properties": {}}
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "create",
+                "description": "Create a document in the current collection.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "data": {"type": "object", "description": "Document fields to store."}
+                    },
+                    "required": ["data"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "query",
+                "description": "Query documents in the current collection by filter.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "filters": {"type": "object", "description": "MongoDB-style filter object."},
+                        "limit": {"type": "integer", "description": "Maximum documents to return.", "default": 100}
+                    }
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "count",
+                "description": "Count documents in the current collection matching a filter.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "filters": {"type": "object", "description": "MongoDB-style filter object."}
+                    }
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "update",
+                "description": "Update a document by id in the current collection.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "document_id": {"type": "string"},
+                        "data": {"type": "object", "description": "Fields to merge into the document."}
+                    },
+                    "required": ["document_id", "data"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "delete",
+                "description": "Delete a document by id from the current collection.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "document_id": {"type": "string"}
+                    },
+                    "required": ["document_id"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "vector_search",
+                "description": "Find documents in the current collection by vector similarity.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "vector": {"type": "array", "items": {"type": "number"}},
+                        "limit": {"type": "integer", "default": 10},
+                        "dimensions": {"type": "integer", "description": "Defaults to the vector length."}
+                    },
+                    "required": ["vector"]
+                }
+            }
+        }
+    ])
+}
+
+/// Maps a single tool call from the LLM onto the matching `MSG_*` request and runs it.
+fn execute_tool_call(client: &mut NexaClient, name: &str, arguments: &Value) -> Result<Value> {
+    match name {
+        "collections" => client.send_message(MSG_LIST_COLLECTIONS, &serde_json::json!({})),
+        "create" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let data = arguments.get("data").cloned().unwrap_or(serde_json::json!({}));
+            let msg = serde_json::json!({ "collection": collection, "data": data });
+            client.send_message(MSG_CREATE, &msg)
+        }
+        "query" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let filters = arguments.get("filters").cloned().unwrap_or(serde_json::json!({}));
+            let limit = arguments.get("limit").and_then(|l| l.as_u64()).unwrap_or(100);
+            let msg = serde_json::json!({ "collection": collection, "filters": filters, "limit": limit });
+            client.send_message(MSG_QUERY, &msg)
+        }
+        "count" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let filters = arguments.get("filters").cloned().unwrap_or(serde_json::json!({}));
+            let msg = serde_json::json!({ "collection": collection, "filters": filters, "limit": 0 });
+            client.send_message(MSG_QUERY, &msg)
+        }
+        "update" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let document_id = arguments
+                .get("document_id")
+                .and_then(|d| d.as_str())
+                .context("update requires a document_id")?;
+            let data = arguments.get("data").cloned().unwrap_or(serde_json::json!({}));
+            let msg = serde_json::json!({ "collection": collection, "document_id": document_id, "data": data });
+            client.send_message(MSG_UPDATE, &msg)
+        }
+        "delete" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let document_id = arguments
+                .get("document_id")
+                .and_then(|d| d.as_str())
+                .context("delete requires a document_id")?;
+            let msg = serde_json::json!({ "collection": collection, "document_id": document_id });
+            client.send_message(MSG_DELETE, &msg)
+        }
+        "vector_search" => {
+            let collection = client
+                .current_collection
+                .clone()
+                .context("No collection selected. Use 'use <collection>' first.")?;
+            let vector = arguments
+                .get("vector")
+                .and_then(|v| v.as_array())
+                .context("vector_search requires a vector")?
+                .clone();
+            let limit = arguments.get("limit").and_then(|l| l.as_u64()).unwrap_or(10);
+            let dimensions = arguments
+                .get("dimensions")
+                .and_then(|d| d.as_u64())
+                .unwrap_or(vector.len() as u64);
+            let msg = serde_json::json!({
+                "collection": collection,
+                "vector": vector,
+                "limit": limit,
+                "dimensions": dimensions
+            });
+            client.send_message(MSG_VECTOR_SEARCH, &msg)
+        }
+        other => anyhow::bail!("Unknown tool: {}", other),
+    }
+}
+
+/// Sends the conversation and tool manifest to the configured chat-completion endpoint.
+fn call_llm(endpoint: &str, model: &str, messages: &[Value], tools: &Value) -> Result<Value> {
+    let body = serde_json::json!({
+        "model": model,
+        "messages": messages,
+        "tools": tools
+    });
+
+    ureq::post(endpoint)
+        .set("Content-Type", "application/json")
+        .send_json(body)
+        .context("Failed to reach LLM endpoint")?
+        .into_json()
+        .context("Failed to parse LLM response")
+}
+
+/// Tools whose results are safe to reuse within a single `ask` run. `create`/`update`/`delete`
+/// are mutations and must always reach the server, even if the model repeats the same call.
+const CACHEABLE_TOOLS: &[&str] = &["collections", "query", "count", "vector_search"];
+
+/// Drives a multi-step tool-calling loop so the model can operate the DB conversationally.
+fn handle_ask(client: &mut NexaClient, text: &str) -> Result<()> {
+    if text.is_empty() {
+        println!("{}", "âœ— Question required".red());
+        println!("{}", "Usage: ask <natural language question>".blue());
+        return Ok(());
+    }
+
+    let max_steps = client.llm_max_steps;
+
+    let collections = client
+        .send_message(MSG_LIST_COLLECTIONS, &serde_json::json!({}))
+        .ok()
+        .and_then(|r| r.get("collections").cloned())
+        .unwrap_or(serde_json::json!([]));
+
+    let system_prompt = format!(
+        "You are an assistant that operates the NexaDB CLI on behalf of a user via tool calls. \
+         Current collection: {}. Available collections: {}. \
+         Call tools as needed to satisfy the request, then reply with plain text and no further \
+         tool calls once you have the final answer.",
+        client.current_collection.as_deref().unwrap_or("(none selected)"),
+        collections
+    );
+
+    let mut messages = vec![
+        serde_json::json!({"role": "system", "content": system_prompt}),
+        serde_json::json!({"role": "user", "content": text}),
+    ];
+    let tools = tool_manifest();
+    let mut cache: HashMap<String, Value> = HashMap::new();
+
+    for _ in 0..max_steps {
+        let response = call_llm(&client.llm_endpoint, &client.llm_model, &messages, &tools)?;
+        let message = response
+            .get("choices")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("message"))
+            .context("LLM response missing choices[0].message")?;
+
+        let tool_calls = message
+            .get("tool_calls")
+            .and_then(|t| t.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        if tool_calls.is_empty() {
+            let content = message.get("content").and_then(|c| c.as_str()).unwrap_or("(no response)");
+            println!("{}", content.cyan());
+            return Ok(());
+        }
+
+        messages.push(message.clone());
+
+        for call in &tool_calls {
+            let id = call.get("id").and_then(|i| i.as_str()).unwrap_or("").to_string();
+            let name = call
+                .get("function")
+                .and_then(|f| f.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or("")
+                .to_string();
+            let raw_args = call
+                .get("function")
+                .and_then(|f| f.get("arguments"))
+                .and_then(|a| a.as_str())
+                .unwrap_or("{}")
+                .to_string();
+            let arguments: Value = serde_json::from_str(&raw_args).unwrap_or(serde_json::json!({}));
+
+            println!("{}", format!("â†’ {} {}", name, raw_args).blue());
+
+            let cacheable = CACHEABLE_TOOLS.contains(&name.as_str());
+            let cache_key = format!("{}:{}", name, raw_args);
+            let result = match cache.get(&cache_key).filter(|_| cacheable) {
+                Some(cached) => cached.clone(),
+                None => {
+                    let outcome = execute_tool_call(client, &name, &arguments)
+                        .unwrap_or_else(|e| serde_json::json!({"error": e.to_string()}));
+                    if cacheable {
+                        cache.insert(cache_key, outcome.clone());
+                    } else {
+                        // A write may have changed what earlier reads returned.
+                        cache.clear();
+                    }
+                    outcome
+                }
+            };
+
+            messages.push(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": id,
+                "content": serde_json::to_string(&result)?
+            }));
+        }
+    }
+
+    println!("{}", "âš  Reached max agent steps without a final answer".yellow());
+    Ok(())
+}
+
+/// Prints a server response as JSON for `--output json`/`--output compact`.
+fn print_value(output: OutputFormat, value: &Value) -> Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Compact => println!("{}", serde_json::to_string(value)?),
+        OutputFormat::Table => unreachable!("table output is rendered per-command"),
+    }
+    Ok(())
+}
+
+fn print_stats_table(stats: &Value) {
+    println!("{}", "âœ“ NexaDB Server Stats".green().bold());
+
+    if let Some(uptime) = stats.get("uptime_seconds").and_then(|u| u.as_u64()) {
+        println!("  Uptime: {}s", uptime);
+    }
+
+    if let Some(collections) = stats.get("collections").and_then(|c| c.as_array()) {
+        println!();
+        println!(
+            "{:<20} {:>12} {:>14} {:>18}",
+            "Collection", "Documents", "Index (B)", "Vector Mem (B)"
+        );
+        for col in collections {
+            let name = col.get("name").and_then(|n| n.as_str()).unwrap_or("unknown");
+            let docs = col.get("documents").and_then(|d| d.as_u64()).unwrap_or(0);
+            let index_size = col.get("index_size_bytes").and_then(|i| i.as_u64()).unwrap_or(0);
+            let vector_mem = col.get("vector_index_memory_bytes").and_then(|v| v.as_u64()).unwrap_or(0);
+            println!("{:<20} {:>12} {:>14} {:>18}", name, docs, index_size, vector_mem);
+        }
+    }
+
+    if let Some(latency) = stats.get("query_latency_ms").and_then(|l| l.as_object()) {
+        println!();
+        println!("{}", "Query latency (ms):".bold());
+        for (percentile, value) in latency {
+            println!("  {:<6} {}", percentile, value);
+        }
+    }
+}
+
+/// Converts a "p50"/"p95"/"p99"-style label into the numeric quantile (0.5, 0.95, 0.99, ...)
+/// Prometheus summary conventions expect, e.g. for `histogram_quantile`/`summary` queries.
+fn parse_quantile(label: &str) -> Option<f64> {
+    let digits = label.strip_prefix('p').or_else(|| label.strip_prefix('P'))?;
+    let percentile: f64 = digits.parse().ok()?;
+    Some(percentile / 100.0)
+}
+
+/// Escapes a string for use as a Prometheus exposition-format label value, per the format's
+/// rules: backslash and double-quote are backslash-escaped, newlines become `\n`.
+fn escape_prometheus_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn print_stats_prometheus(stats: &Value) {
+    if let Some(uptime) = stats.get("uptime_seconds").and_then(|u| u.as_u64()) {
+        println!("nexa_uptime_seconds {}", uptime);
+    }
+
+    if let Some(collections) = stats.get("collections").and_then(|c| c.as_array()) {
+        for col in collections {
+            let name = col.get("name").and_then(|n| n.as_str()).unwrap_or("unknown");
+            let name = escape_prometheus_label(name);
+            if let Some(docs) = col.get("documents").and_then(|d| d.as_u64()) {
+                println!("nexa_documents_total{{collection=\"{}\"}} {}", name, docs);
+            }
+            if let Some(index_size) = col.get("index_size_bytes").and_then(|i| i.as_u64()) {
+                println!("nexa_index_size_bytes{{collection=\"{}\"}} {}", name, index_size);
+            }
+            if let Some(vector_mem) = col.get("vector_index_memory_bytes").and_then(|v| v.as_u64()) {
+                println!("nexa_vector_index_memory_bytes{{collection=\"{}\"}} {}", name, vector_mem);
+            }
+        }
+    }
+
+    if let Some(latency) = stats.get("query_latency_ms").and_then(|l| l.as_object()) {
+        for (percentile, value) in latency {
+            if let Some(v) = value.as_f64() {
+                match parse_quantile(percentile) {
+                    Some(quantile) => println!("nexa_query_latency_ms{{quantile=\"{}\"}} {}", quantile, v),
+                    None => println!("nexa_query_latency_ms{{percentile=\"{}\"}} {}", percentile, v),
+                }
+            }
+        }
+    }
+}
+
 fn print_banner() {
     let banner = r#"
 â•”â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•â•—
@@ -181,6 +713,16 @@ Vector Search:
   vector_search <vector> [limit] [dimensions]
                                 Search by vector similarity
 
+Batch Operations:
+  batch [--atomic] <json array>
+                                Run create/update/delete ops in one round-trip
+
+Observability:
+  stats [--prometheus]           Show server runtime metrics
+
+AI Agent:
+  ask <question>                Let an LLM drive the DB via tool calls
+
 Examples:
   use movies
   create {"title": "The Matrix", "year": 1999}
@@ -189,6 +731,7 @@ Examples:
   delete doc_abc123
   vector_search [0.1, 0.95, 0.1, 0.8] 3 4
   count {"status": "active"}
+  batch --atomic [{"op": "create", "data": {"title": "Dune"}}, {"op": "delete", "document_id": "doc_abc123"}]
 
 System:
   help                          Show this help
@@ -229,6 +772,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
         "collections" => {
             let msg = serde_json::json!({});
             match client.send_message(MSG_LIST_COLLECTIONS, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     if let Some(collections) = result.get("collections").and_then(|c| c.as_array()) {
                         if collections.is_empty() {
@@ -270,6 +814,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_CREATE, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     let doc_id = result.get("document_id")
                         .and_then(|id| id.as_str())
@@ -300,6 +845,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_QUERY, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     if let Some(docs) = result.get("documents").and_then(|d| d.as_array()) {
                         if docs.is_empty() {
@@ -345,6 +891,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_UPDATE, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     println!("{}", format!("âœ“ Document updated: {}", doc_id).green());
                     println!("{}", serde_json::to_string_pretty(&result)?.cyan());
@@ -370,6 +917,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_DELETE, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(_) => {
                     println!("{}", format!("âœ“ Document deleted: {}", doc_id).green());
                 }
@@ -397,6 +945,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_QUERY, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     if let Some(count) = result.get("count").and_then(|c| c.as_u64()) {
                         println!("{}", format!("âœ“ Document count: {}", count).green());
@@ -437,6 +986,7 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
             });
 
             match client.send_message(MSG_VECTOR_SEARCH, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
                 Ok(result) => {
                     if let Some(results) = result.get("results").and_then(|r| r.as_array()) {
                         if results.is_empty() {
@@ -460,6 +1010,79 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
                 Err(e) => println!("{}", format!("âœ— Error: {}", e).red()),
             }
         }
+        "batch" => {
+            if client.current_collection.is_none() {
+                println!("{}", "âœ— No collection selected. Use 'use <collection>' first.".red());
+                return Ok(false);
+            }
+
+            let rest = line.trim_start_matches("batch").trim();
+            let (atomic, json_str) = match rest.strip_prefix("--atomic") {
+                Some(remainder) => (true, remainder.trim()),
+                None => (false, rest),
+            };
+
+            if json_str.is_empty() {
+                println!("{}", "âœ— JSON array of operations required".red());
+                println!("{}", "Usage: batch [--atomic] <json array>".blue());
+                return Ok(false);
+            }
+
+            let operations: Value = serde_json::from_str(json_str)?;
+            if !operations.is_array() {
+                println!("{}", "âœ— Batch payload must be a JSON array of operations".red());
+                return Ok(false);
+            }
+
+            let msg = serde_json::json!({
+                "collection": client.current_collection,
+                "atomic": atomic,
+                "operations": operations
+            });
+
+            match client.send_message(MSG_BATCH, &msg) {
+                Ok(result) if client.output != OutputFormat::Table => print_value(client.output, &result)?,
+                Ok(result) => {
+                    if let Some(results) = result.get("results").and_then(|r| r.as_array()) {
+                        println!("{}", format!("âœ“ Batch completed: {} operation(s)", results.len()).green());
+                        for (i, item) in results.iter().enumerate() {
+                            let ok = item.get("success").and_then(|s| s.as_bool()).unwrap_or(false);
+                            if ok {
+                                let doc_id = item.get("document_id").and_then(|d| d.as_str()).unwrap_or("N/A");
+                                println!("  [{}] {} {}", i + 1, "âœ“".green(), doc_id);
+                            } else {
+                                let err = item.get("error").and_then(|e| e.as_str()).unwrap_or("Unknown error");
+                                println!("  [{}] {} {}", i + 1, "âœ—".red(), err);
+                            }
+                        }
+                    } else {
+                        println!("{}", "âœ“ Batch submitted".green());
+                    }
+                }
+                Err(e) => println!("{}", format!("âœ— Error: {}", e).red()),
+            }
+        }
+        "stats" => {
+            let prometheus = line.trim_start_matches("stats").trim() == "--prometheus";
+            match client.send_message(MSG_STATS, &serde_json::json!({})) {
+                Ok(result) => {
+                    if prometheus {
+                        print_stats_prometheus(&result);
+                    } else if client.output != OutputFormat::Table {
+                        print_value(client.output, &result)?;
+                    } else {
+                        print_stats_table(&result);
+                    }
+                }
+                Err(e) => println!("{}", format!("âœ— Error: {}", e).red()),
+            }
+        }
+        "ask" => {
+            let text = line.trim_start_matches("ask").trim();
+            if let Err(e) = handle_ask(client, text) {
+                println!("{}", format!("âœ— Error: {}", e).red());
+            }
+        }
         _ => {
             println!("{}", format!("âœ— Unknown command: {}", cmd).red());
             println!("{}", "Type 'help' to see available commands".blue());
@@ -472,6 +1095,10 @@ fn handle_command(client: &mut NexaClient, line: &str) -> Result<bool> {
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+
     // Get password
     let password = if args.password {
         rpassword::prompt_password("Password: ")?
@@ -479,19 +1106,60 @@ fn main() -> Result<()> {
         "nexadb123".to_string()
     };
 
-    print_banner();
+    let scripting = args.exec.is_some() || args.file.is_some();
+
+    if !scripting {
+        print_banner();
+    }
 
     // Connect to NexaDB
-    let mut client = match NexaClient::connect(&args.host, args.port, &args.username, &password) {
+    let config = ClientConfig {
+        llm_endpoint: args.llm_endpoint.clone(),
+        llm_model: args.llm_model.clone(),
+        llm_max_steps: args.llm_max_steps,
+        output: args.output,
+        max_payload_len: args.max_payload_len,
+        quiet: scripting,
+    };
+
+    let mut client = match NexaClient::connect(&args.host, args.port, &args.username, &password, config) {
         Ok(c) => c,
         Err(e) => {
-            println!("{}", format!("âœ— Connection failed: {}", e).red());
-            println!("{}", "Make sure NexaDB server is running:".blue());
-            println!("{}", "  $ nexadb start".blue());
+            if scripting {
+                eprintln!("Connection failed: {}", e);
+            } else {
+                println!("{}", format!("âœ— Connection failed: {}", e).red());
+                println!("{}", "Make sure NexaDB server is running:".blue());
+                println!("{}", "  $ nexadb start".blue());
+            }
             std::process::exit(1);
         }
     };
 
+    if let Some(command) = &args.exec {
+        handle_command(&mut client, command)?;
+        return Ok(());
+    }
+
+    if let Some(path) = &args.file {
+        let script = std::fs::read_to_string(path).context("Failed to read script file")?;
+        for line in script.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match handle_command(&mut client, line) {
+                Ok(should_exit) => {
+                    if should_exit {
+                        break;
+                    }
+                }
+                Err(e) => eprintln!("Error: {}", e),
+            }
+        }
+        return Ok(());
+    }
+
     // Start REPL
     let mut rl = DefaultEditor::new()?;
     let history_file = dirs::home_dir()